@@ -0,0 +1,146 @@
+// Real-time playback: everything else in this crate is sample-in/sample-out
+// with no host integration, so this module is the thin layer that actually
+// gets those samples to a speaker. It's gated behind the `audio` feature so
+// crates that only want the DSP don't pull in cpal.
+#![cfg(feature = "audio")]
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, Stream, StreamConfig};
+use ringbuf::{HeapConsumer, HeapRb};
+
+use crate::delay::{seconds_to_samples, MultiTapDelay, SimpleDelay};
+
+// How many pre-rendered samples the control thread is allowed to stay ahead
+// of the audio callback by. Generous enough to absorb scheduling jitter
+// without adding noticeable latency.
+const RING_CAPACITY: usize = 4096;
+
+// Anything that can be pulled one sample at a time from the control thread
+// to feed the ring: a bare closure, or one of the delay types ticking
+// against a fixed set of parameters.
+pub trait Source: Send + 'static {
+    fn next_sample(&mut self) -> f32;
+}
+
+impl<F: FnMut() -> f32 + Send + 'static> Source for F {
+    fn next_sample(&mut self) -> f32 {
+        self()
+    }
+}
+
+// Feeds a SimpleDelay with silence as its input and taps its wet output, so
+// what it plays is whatever the delay already holds, recirculated through
+// the feedback path (DC blocking, tempo sync). A delay whose buffer is still
+// empty plays silence; no separate input source is wired in.
+pub struct SimpleDelaySource {
+    pub delay: SimpleDelay,
+    pub delay_samples: f32,
+    pub trigger: f32,
+    pub feedback_amount: f32,
+}
+
+impl Source for SimpleDelaySource {
+    fn next_sample(&mut self) -> f32 {
+        self.delay
+            .tick_wet(0.0, self.delay_samples, self.trigger, self.feedback_amount)
+    }
+}
+
+pub struct MultiTapDelaySource {
+    pub delay: MultiTapDelay,
+    pub feedback_amount: f32,
+}
+
+impl Source for MultiTapDelaySource {
+    fn next_sample(&mut self) -> f32 {
+        self.delay.tick(0.0, self.feedback_amount)
+    }
+}
+
+// Opens the host's default output device and streams `source` through it
+// until the returned `Stream` is dropped. The stream's negotiated sample
+// rate is handed back so callers can convert delay times given in seconds
+// via `delay_seconds_to_samples` correctly regardless of device.
+pub fn play_default_output(
+    mut source: impl Source,
+) -> Result<(Stream, u32), Box<dyn std::error::Error>> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or("no default output device")?;
+    let supported_config = device.default_output_config()?;
+    let sample_format = supported_config.sample_format();
+    let config: StreamConfig = supported_config.into();
+    let sample_rate = config.sample_rate.0;
+    let channels = config.channels as usize;
+
+    // Real lock-free SPSC handoff between the control thread, which renders
+    // `source`, and the audio callback, which must never block or allocate.
+    let rb = HeapRb::<f32>::new(RING_CAPACITY);
+    let (mut producer, consumer) = rb.split();
+
+    std::thread::spawn(move || loop {
+        let mut sample = source.next_sample();
+        // A full ring means the render thread is RING_CAPACITY samples
+        // ahead of the callback; wait for it to drain instead of dropping
+        // the sample, which would skip ahead in `source`'s output.
+        while let Err(rejected) = producer.push(sample) {
+            sample = rejected;
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    });
+
+    let stream = build_stream(&device, &config, sample_format, consumer, channels)?;
+    stream.play()?;
+    Ok((stream, sample_rate))
+}
+
+fn build_stream(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    sample_format: SampleFormat,
+    consumer: HeapConsumer<f32>,
+    channels: usize,
+) -> Result<Stream, Box<dyn std::error::Error>> {
+    // `SampleFormat` is `#[non_exhaustive]`, so match only the formats we
+    // actually support and fall back to an error for the rest rather than
+    // trying to enumerate every current and future variant.
+    match sample_format {
+        SampleFormat::F32 => Ok(build_stream_typed::<f32>(device, config, consumer, channels)?),
+        SampleFormat::I16 => Ok(build_stream_typed::<i16>(device, config, consumer, channels)?),
+        SampleFormat::U16 => Ok(build_stream_typed::<u16>(device, config, consumer, channels)?),
+        other => Err(format!("unsupported sample format: {other:?}").into()),
+    }
+}
+
+fn build_stream_typed<T>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    mut consumer: HeapConsumer<f32>,
+    channels: usize,
+) -> Result<Stream, cpal::BuildStreamError>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], _info: &cpal::OutputCallbackInfo| {
+            for frame in data.chunks_mut(channels) {
+                // An empty ring means an underrun; fall back to silence
+                // rather than blocking the callback.
+                let sample = T::from_sample(consumer.pop().unwrap_or(0.0));
+                for out in frame.iter_mut() {
+                    *out = sample;
+                }
+            }
+        },
+        |err| eprintln!("audio output stream error: {}", err),
+        None,
+    )
+}
+
+// Convenience re-export so callers configuring delay times in seconds don't
+// need to import the delay module directly just for this conversion.
+pub fn delay_seconds_to_samples(seconds: f32, sample_rate: u32) -> f32 {
+    seconds_to_samples(seconds, sample_rate)
+}
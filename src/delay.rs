@@ -21,34 +21,126 @@ impl CircularBuffer {
             self.write_index = 0;
         }
     }
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
     // Callers read from the circular buffer at a specified distance from the
     // write index, i.e., samples that were inserted N write operations ago,
     // where N is length_samples. Conversion from units such as seconds to
     // samples, or interpolation between multiple read values are higher-level
     // concerns, handled by callers.
-    pub fn read(&self, length_samples: usize) -> f32 {
-        if length_samples > self.buffer.len() {
-            panic!("Requested delay length is greater than buffer size!");
-        }
-        // usize::min_value() == 0, so we can't subtract two of them and think
-        // about whether the result is negative. We convert our usizes to i32
-        // here to handle this.
-        let mut read_index = self.write_index as i32 - length_samples as i32;
+    //
+    // Takes a signed offset rather than a `usize` so taps that sit one
+    // sample either side of an integer read position (see
+    // `read_interpolated` below) can be expressed without extra wrapping
+    // logic at the call site.
+    fn read_offset(&self, length_samples: i32) -> f32 {
+        let mut read_index = self.write_index as i32 - length_samples;
+        let len = self.buffer.len() as i32;
         if read_index < 0 {
-            read_index += self.buffer.len() as i32;
+            read_index += len;
+        } else if read_index >= len {
+            read_index -= len;
         }
-
         self.buffer[read_index as usize]
     }
+
+    // Reads at a fractional distance from the write index using 4-point
+    // Hermite interpolation, which gives a much smoother result than linear
+    // interpolation for modulated delay times (chorus, flanger, pitch-shift)
+    // while remaining cheap enough for per-sample use.
+    pub fn read_interpolated(&self, length_samples: f32) -> f32 {
+        if length_samples > self.buffer.len() as f32 {
+            panic!("Requested delay length is greater than buffer size!");
+        }
+
+        let idx = length_samples.trunc() as i32;
+        let frac = length_samples.fract();
+
+        // Taps run from newer (xm1) to older (x2), so `frac` moves the read
+        // from offset `idx` toward offset `idx + 1`. The taps older than the
+        // integer read position can underflow past the start of the buffer's
+        // valid history; clamp them to 0.0 rather than wrapping around to
+        // unrelated future samples.
+        let len = self.buffer.len() as i32;
+        let xm1 = self.read_offset(idx - 1);
+        let x0 = self.read_offset(idx);
+        let x1 = if idx + 1 > len { 0.0 } else { self.read_offset(idx + 1) };
+        let x2 = if idx + 2 > len { 0.0 } else { self.read_offset(idx + 2) };
+
+        let c0 = x0;
+        let c1 = 0.5 * (x1 - xm1);
+        let c2 = xm1 - 2.5 * x0 + 2.0 * x1 - 0.5 * x2;
+        let c3 = 0.5 * (x2 - xm1) + 1.5 * (x0 - x1);
+
+        ((c3 * frac + c2) * frac + c1) * frac + c0
+    }
 }
 
-fn seconds_to_samples(seconds: f32, sample_rate: u32) -> f32 {
+// Only consumed by the `audio` module today, which is itself feature-gated.
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+pub(crate) fn seconds_to_samples(seconds: f32, sample_rate: u32) -> f32 {
     sample_rate as f32 * seconds
 }
 
+// A one-pole high-pass filter used to strip the DC offset that accumulates
+// in a delay's feedback path, which would otherwise drift the signal toward
+// a rail at high feedback amounts.
+struct DcBlocker {
+    xm1: f32,
+    ym1: f32,
+    r: f32,
+}
+
+impl DcBlocker {
+    fn new() -> DcBlocker {
+        DcBlocker {
+            xm1: 0.0,
+            ym1: 0.0,
+            r: 0.995,
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = x - self.xm1 + self.r * self.ym1;
+        self.xm1 = x;
+        self.ym1 = y;
+        y
+    }
+
+    // The pole needs to sit closer to 1.0 at high sample rates to keep the
+    // cutoff frequency roughly constant.
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.r = if sample_rate > 90_000 { 0.997 } else { 0.995 };
+    }
+
+    fn reset(&mut self) {
+        self.xm1 = 0.0;
+        self.ym1 = 0.0;
+    }
+}
+
+// A trigger input above this level, after having been below it, is read as a
+// rising edge for tempo-sync purposes.
+const TRIGGER_THRESHOLD: f32 = 0.5;
+
+// SimpleDelay's delay time either comes straight from the `delay_samples`
+// argument to `tick` (Time) or is derived from the interval between trigger
+// pulses (Sync), so it can lock to an external clock/tempo without the
+// caller converting BPM to samples itself.
+pub enum DelayMode {
+    Time,
+    Sync,
+}
+
 // SimpleDelay manages its own buffer
 pub struct SimpleDelay {
     buffer: CircularBuffer,
+    dc_blocker: DcBlocker,
+    mode: DelayMode,
+    sample_counter: usize,
+    synced_delay_samples: f32,
+    trigger_was_high: bool,
 }
 
 // Parameters are provided as inputs to the delay. In general this provides
@@ -58,13 +150,241 @@ impl SimpleDelay {
     pub fn new(buffer_size: usize) -> SimpleDelay {
         SimpleDelay {
             buffer: CircularBuffer::new(buffer_size),
+            dc_blocker: DcBlocker::new(),
+            mode: DelayMode::Time,
+            sample_counter: 0,
+            synced_delay_samples: 0.0,
+            trigger_was_high: false,
         }
     }
+    pub fn set_mode(&mut self, mode: DelayMode) {
+        self.mode = mode;
+    }
+    // Returns the raw wet (delayed) signal, for callers who want to route it
+    // elsewhere instead of (or in addition to) blending it with the dry
+    // input here. `tick` below is a thin dry/wet wrapper around this.
+    pub fn tick_wet(
+        &mut self,
+        input_sample: f32,
+        delay_samples: f32,
+        trigger: f32,
+        feedback_amount: f32,
+    ) -> f32 {
+        let delay_samples = match self.mode {
+            DelayMode::Time => delay_samples,
+            DelayMode::Sync => {
+                self.sample_counter += 1;
+
+                let trigger_high = trigger > TRIGGER_THRESHOLD;
+                if trigger_high && !self.trigger_was_high {
+                    // A trigger interval longer than the buffer can hold
+                    // (slow tempo, undersized buffer, late first pulse)
+                    // would otherwise panic in read_interpolated; clamp it
+                    // to the longest delay the buffer can actually provide.
+                    self.synced_delay_samples =
+                        (self.sample_counter as f32).min(self.buffer.len() as f32);
+                    self.sample_counter = 0;
+                }
+                self.trigger_was_high = trigger_high;
+
+                self.synced_delay_samples
+            }
+        };
+
+        let output = self.buffer.read_interpolated(delay_samples);
+        let fed_back = self.dc_blocker.process(input_sample + (output * feedback_amount));
+        self.buffer.write(fed_back);
+        output
+    }
+    // Standard delay-node contract: inp/fb/mix/sig. `mix` of 0.0 is fully
+    // dry, 1.0 is fully wet; feedback amount is independent of this blend.
+    pub fn tick(
+        &mut self,
+        input_sample: f32,
+        delay_samples: f32,
+        trigger: f32,
+        feedback_amount: f32,
+        mix: f32,
+    ) -> f32 {
+        let wet = self.tick_wet(input_sample, delay_samples, trigger, feedback_amount);
+        (1.0 - mix) * input_sample + mix * wet
+    }
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.dc_blocker.set_sample_rate(sample_rate);
+    }
+    pub fn reset(&mut self) {
+        self.dc_blocker.reset();
+    }
+}
+
+// A delay-time change smaller than this is treated as ordinary modulation
+// (vibrato-rate LFOs, fine automation) and applied immediately rather than
+// triggering a crossfade.
+const DELAY_CHANGE_THRESHOLD: f32 = 1.0;
+
+// ModulatedDelay wraps a CircularBuffer and crossfades between the old and
+// new read position whenever the delay time jumps by more than a small
+// threshold, so LFO sweeps and automation don't produce the click a bare
+// read-pointer jump would cause.
+pub struct ModulatedDelay {
+    buffer: CircularBuffer,
+    current_delay: f32,
+    old_delay: f32,
+    fading: bool,
+    fade_position: usize,
+    fade_length: usize,
+}
+
+impl ModulatedDelay {
+    pub fn new(buffer_size: usize, fade_length_samples: usize) -> ModulatedDelay {
+        ModulatedDelay {
+            buffer: CircularBuffer::new(buffer_size),
+            current_delay: 0.0,
+            old_delay: 0.0,
+            fading: false,
+            fade_position: 0,
+            fade_length: fade_length_samples.max(1),
+        }
+    }
+
+    pub fn fade_length_samples(&self) -> usize {
+        self.fade_length
+    }
+
+    // A fade length of 0 would make the crossfade's mix coefficient a 0/0
+    // division; treat it as "snap instantly" by flooring to 1 sample instead.
+    pub fn set_fade_length_samples(&mut self, fade_length_samples: usize) {
+        self.fade_length = fade_length_samples.max(1);
+    }
+
     pub fn tick(&mut self, input_sample: f32, delay_samples: f32, feedback_amount: f32) -> f32 {
-        let output = self.buffer.read(delay_samples as usize);
+        // A jump while no fade is in flight opens a new crossfade from
+        // wherever we currently are. A jump that lands mid-fade doesn't
+        // restart the crossfade (that would never let it converge); instead
+        // `current_delay` below keeps tracking the latest target every
+        // tick, so an in-flight fade follows a continuing sweep rather than
+        // freezing on the value sampled when it started.
+        if !self.fading && (delay_samples - self.current_delay).abs() > DELAY_CHANGE_THRESHOLD {
+            self.old_delay = self.current_delay;
+            self.fading = true;
+            self.fade_position = 0;
+        }
+        self.current_delay = delay_samples;
+
+        let new_tap = self.buffer.read_interpolated(self.current_delay);
+        let output = if self.fading {
+            let old_tap = self.buffer.read_interpolated(self.old_delay);
+            let g = self.fade_position as f32 / self.fade_length as f32;
+            self.fade_position += 1;
+            if self.fade_position >= self.fade_length {
+                self.fading = false;
+            }
+            (1.0 - g) * old_tap + g * new_tap
+        } else {
+            new_tap
+        };
+
         self.buffer.write(input_sample + (output * feedback_amount));
         output
     }
 }
 
-pub struct DelayTap(pub f32, pub f32);
\ No newline at end of file
+pub struct DelayTap(pub f32, pub f32);
+
+// MultiTapDelay shares a single CircularBuffer across any number of taps, so
+// rhythmic echo patterns and early-reflection clusters cost one buffer
+// instead of one SimpleDelay per tap.
+pub struct MultiTapDelay {
+    buffer: CircularBuffer,
+    taps: Vec<DelayTap>,
+}
+
+impl MultiTapDelay {
+    pub fn new(buffer_size: usize) -> MultiTapDelay {
+        MultiTapDelay {
+            buffer: CircularBuffer::new(buffer_size),
+            taps: Vec::new(),
+        }
+    }
+
+    pub fn add_tap(&mut self, delay_samples: f32, gain: f32) {
+        self.taps.push(DelayTap(delay_samples, gain));
+    }
+
+    pub fn clear_taps(&mut self) {
+        self.taps.clear();
+    }
+
+    pub fn set_taps(&mut self, taps: Vec<DelayTap>) {
+        self.taps = taps;
+    }
+
+    // Feedback is drawn from the longest tap, matching the behavior a single
+    // feedback-delay-line tap would have had before it grew extra taps.
+    fn longest_tap_time(&self) -> f32 {
+        self.taps
+            .iter()
+            .map(|tap| tap.0)
+            .fold(0.0, |longest: f32, time| longest.max(time))
+    }
+
+    pub fn tick(&mut self, input: f32, feedback_amount: f32) -> f32 {
+        // Read every tap, including the feedback tap, against the write
+        // index as it stood before this tick's write, matching the
+        // read-before-write convention SimpleDelay/ModulatedDelay use.
+        let feedback_sample = self.buffer.read_interpolated(self.longest_tap_time());
+        let output = self
+            .taps
+            .iter()
+            .map(|tap| self.buffer.read_interpolated(tap.0) * tap.1)
+            .sum();
+
+        self.buffer
+            .write(input + (feedback_sample * feedback_amount));
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled_buffer() -> CircularBuffer {
+        let mut buf = CircularBuffer::new(8);
+        for i in 0..8 {
+            buf.write(i as f32);
+        }
+        buf
+    }
+
+    #[test]
+    fn read_interpolated_integer_offset_matches_written_sample() {
+        let buf = filled_buffer();
+        assert_eq!(buf.read_interpolated(1.0), 7.0);
+        assert_eq!(buf.read_interpolated(2.0), 6.0);
+        assert_eq!(buf.read_interpolated(4.0), 4.0);
+    }
+
+    #[test]
+    fn read_interpolated_fractional_offset_interpolates_between_neighbors() {
+        let buf = filled_buffer();
+        // Away from the buffer's wrap point the written samples form a
+        // straight line, so a fractional read should reduce to plain
+        // linear interpolation between its two integer neighbors.
+        assert_eq!(buf.read_interpolated(4.5), 3.5);
+    }
+
+    #[test]
+    fn tick_one_sample_tap_lags_by_one_sample() {
+        // Matches SimpleDelay::tick's read-before-write timing: a tap
+        // configured for delay 1 echoes the *previous* input, not the one
+        // just written.
+        let mut delay = MultiTapDelay::new(8);
+        delay.add_tap(1.0, 1.0);
+
+        assert_eq!(delay.tick(10.0, 0.0), 0.0);
+        assert_eq!(delay.tick(20.0, 0.0), 10.0);
+        assert_eq!(delay.tick(30.0, 0.0), 20.0);
+    }
+}
\ No newline at end of file
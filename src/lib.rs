@@ -0,0 +1,4 @@
+pub mod delay;
+
+#[cfg(feature = "audio")]
+pub mod audio;